@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Describes why `read_expr`/`read_list` failed to parse their input.
+#[derive(Debug)]
+pub struct ParseError {
+    pub position: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(position: usize, reason: impl Into<String>) -> Self {
+        ParseError {
+            position,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error at byte {}: {}", self.position, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<pom::Error> for ParseError {
+    fn from(err: pom::Error) -> Self {
+        match err {
+            pom::Error::Incomplete => ParseError::new(0, "unexpected end of input"),
+            pom::Error::Mismatch { message, position } => ParseError::new(position, message),
+            pom::Error::Conversion { message, position } => ParseError::new(position, message),
+            pom::Error::Expect {
+                message, position, ..
+            } => ParseError::new(position, message),
+            pom::Error::Custom {
+                message, position, ..
+            } => ParseError::new(position, message),
+        }
+    }
+}
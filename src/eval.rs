@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::LispVal;
+
+/// Errors that can occur while evaluating a parsed `LispVal`.
+#[derive(Debug)]
+pub enum EvalError {
+    UnboundSymbol(String),
+    NotCallable(String),
+    WrongArgCount { expected: String, got: usize },
+    TypeMismatch(String),
+    DivideByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnboundSymbol(name) => write!(f, "unbound symbol: {}", name),
+            EvalError::NotCallable(repr) => write!(f, "not callable: {}", repr),
+            EvalError::WrongArgCount { expected, got } => {
+                write!(f, "wrong number of arguments: expected {}, got {}", expected, got)
+            }
+            EvalError::TypeMismatch(msg) => write!(f, "type mismatch: {}", msg),
+            EvalError::DivideByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// The signature every builtin primitive implements.
+pub type Builtin = fn(Vec<LispVal>) -> Result<LispVal, EvalError>;
+
+/// Registers `name` in `table` as a `LispVal::RustFunction` wrapping `f`.
+pub fn add_builtin(table: &mut HashMap<String, LispVal>, name: &str, f: Builtin) {
+    table.insert(name.to_string(), LispVal::RustFunction(name.to_string(), f));
+}
+
+/// Holds the symbol table a program evaluates against.
+pub struct LispState {
+    table: HashMap<String, LispVal>,
+}
+
+impl LispState {
+    pub fn new() -> Self {
+        let mut table = HashMap::new();
+        add_builtin(&mut table, "+", builtin_add);
+        add_builtin(&mut table, "-", builtin_sub);
+        add_builtin(&mut table, "*", builtin_mul);
+        add_builtin(&mut table, "/", builtin_div);
+        add_builtin(&mut table, "print", builtin_print);
+        add_builtin(&mut table, "car", builtin_car);
+        add_builtin(&mut table, "cdr", builtin_cdr);
+        add_builtin(&mut table, "cons", builtin_cons);
+        LispState { table }
+    }
+
+    pub fn eval(&mut self, expr: &LispVal) -> Result<LispVal, EvalError> {
+        match expr {
+            LispVal::Number(_)
+            | LispVal::Float(_)
+            | LispVal::String(_)
+            | LispVal::Bool(_)
+            | LispVal::Char(_)
+            | LispVal::RustFunction(..) => Ok(expr.clone()),
+            LispVal::Atom(name) => self
+                .table
+                .get(name)
+                .cloned()
+                .ok_or_else(|| EvalError::UnboundSymbol(name.clone())),
+            LispVal::List(items) => self.eval_list(items),
+            LispVal::DottedList(..) => {
+                Err(EvalError::TypeMismatch("cannot evaluate a dotted list".to_string()))
+            }
+        }
+    }
+
+    fn eval_list(&mut self, items: &[LispVal]) -> Result<LispVal, EvalError> {
+        let (head, rest) = items
+            .split_first()
+            .ok_or_else(|| EvalError::NotCallable("()".to_string()))?;
+        let func = self.eval(head)?;
+        let args = rest
+            .iter()
+            .map(|arg| self.eval(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        match func {
+            LispVal::RustFunction(_, f) => f(args),
+            other => Err(EvalError::NotCallable(format!("{:?}", other))),
+        }
+    }
+}
+
+fn numbers_only(args: &[LispVal]) -> Result<Vec<i64>, EvalError> {
+    args.iter()
+        .map(|v| match v {
+            LispVal::Number(n) => Ok(*n),
+            other => Err(EvalError::TypeMismatch(format!(
+                "expected a number, got {:?}",
+                other
+            ))),
+        })
+        .collect()
+}
+
+fn builtin_add(args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    let nums = numbers_only(&args)?;
+    nums.into_iter()
+        .try_fold(0i64, |a, b| a.checked_add(b))
+        .map(LispVal::Number)
+        .ok_or_else(|| EvalError::TypeMismatch("integer overflow".to_string()))
+}
+
+fn builtin_sub(args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    let nums = numbers_only(&args)?;
+    let overflow = || EvalError::TypeMismatch("integer overflow".to_string());
+    match nums.split_first() {
+        None => Err(EvalError::WrongArgCount {
+            expected: "at least 1".to_string(),
+            got: 0,
+        }),
+        Some((first, [])) => first.checked_neg().map(LispVal::Number).ok_or_else(overflow),
+        Some((first, rest)) => rest
+            .iter()
+            .try_fold(*first, |a, b| a.checked_sub(*b))
+            .map(LispVal::Number)
+            .ok_or_else(overflow),
+    }
+}
+
+fn builtin_mul(args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    let nums = numbers_only(&args)?;
+    nums.into_iter()
+        .try_fold(1i64, |a, b| a.checked_mul(b))
+        .map(LispVal::Number)
+        .ok_or_else(|| EvalError::TypeMismatch("integer overflow".to_string()))
+}
+
+fn builtin_div(args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    let nums = numbers_only(&args)?;
+    match nums.split_first() {
+        None => Err(EvalError::WrongArgCount {
+            expected: "at least 1".to_string(),
+            got: 0,
+        }),
+        Some((first, rest)) => {
+            let mut acc = *first;
+            for n in rest {
+                if *n == 0 {
+                    return Err(EvalError::DivideByZero);
+                }
+                acc = acc
+                    .checked_div(*n)
+                    .ok_or_else(|| EvalError::TypeMismatch("integer overflow".to_string()))?;
+            }
+            Ok(LispVal::Number(acc))
+        }
+    }
+}
+
+fn builtin_print(args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    for arg in &args {
+        println!("{}", arg);
+    }
+    Ok(LispVal::List(vec![]))
+}
+
+fn builtin_car(mut args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    if args.len() != 1 {
+        return Err(EvalError::WrongArgCount {
+            expected: "1".to_string(),
+            got: args.len(),
+        });
+    }
+    match args.remove(0) {
+        LispVal::List(mut items) if !items.is_empty() => Ok(items.remove(0)),
+        other => Err(EvalError::TypeMismatch(format!(
+            "car expects a non-empty list, got {:?}",
+            other
+        ))),
+    }
+}
+
+fn builtin_cdr(mut args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    if args.len() != 1 {
+        return Err(EvalError::WrongArgCount {
+            expected: "1".to_string(),
+            got: args.len(),
+        });
+    }
+    match args.remove(0) {
+        LispVal::List(items) if !items.is_empty() => Ok(LispVal::List(items[1..].to_vec())),
+        other => Err(EvalError::TypeMismatch(format!(
+            "cdr expects a non-empty list, got {:?}",
+            other
+        ))),
+    }
+}
+
+fn builtin_cons(mut args: Vec<LispVal>) -> Result<LispVal, EvalError> {
+    if args.len() != 2 {
+        return Err(EvalError::WrongArgCount {
+            expected: "2".to_string(),
+            got: args.len(),
+        });
+    }
+    let tail = args.pop().unwrap();
+    let head = args.pop().unwrap();
+    match tail {
+        LispVal::List(mut items) => {
+            items.insert(0, head);
+            Ok(LispVal::List(items))
+        }
+        other => Ok(LispVal::DottedList(vec![head], Box::new(other))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn eval_self_evaluating() {
+        let mut state = LispState::new();
+        assert_eq!(state.eval(&LispVal::Number(42)).unwrap(), LispVal::Number(42));
+        assert_eq!(state.eval(&LispVal::Bool(true)).unwrap(), LispVal::Bool(true));
+    }
+
+    #[test]
+    fn eval_unbound_symbol() {
+        let mut state = LispState::new();
+        assert!(matches!(
+            state.eval(&LispVal::Atom("nope".to_string())),
+            Err(EvalError::UnboundSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn eval_arithmetic() {
+        let mut state = LispState::new();
+        let expr = LispVal::List(vec![
+            LispVal::Atom("+".to_string()),
+            LispVal::Number(1),
+            LispVal::Number(2),
+            LispVal::Number(3),
+        ]);
+        assert_eq!(state.eval(&expr).unwrap(), LispVal::Number(6));
+    }
+
+    #[test]
+    fn eval_divide_by_zero() {
+        let mut state = LispState::new();
+        let expr = LispVal::List(vec![
+            LispVal::Atom("/".to_string()),
+            LispVal::Number(1),
+            LispVal::Number(0),
+        ]);
+        assert!(matches!(state.eval(&expr), Err(EvalError::DivideByZero)));
+    }
+
+    #[test]
+    fn eval_add_overflow() {
+        let mut state = LispState::new();
+        let expr = LispVal::List(vec![
+            LispVal::Atom("+".to_string()),
+            LispVal::Number(i64::MAX),
+            LispVal::Number(1),
+        ]);
+        assert!(matches!(state.eval(&expr), Err(EvalError::TypeMismatch(_))));
+    }
+
+    #[test]
+    fn eval_mul_overflow() {
+        let mut state = LispState::new();
+        let expr = LispVal::List(vec![
+            LispVal::Atom("*".to_string()),
+            LispVal::Number(i64::MAX),
+            LispVal::Number(2),
+        ]);
+        assert!(matches!(state.eval(&expr), Err(EvalError::TypeMismatch(_))));
+    }
+
+    // car/cdr/cons are exercised directly since they take already-evaluated
+    // arguments; `eval` has no `quote` yet to stop a list literal from being
+    // read as a call.
+    #[test]
+    fn eval_car_cdr_cons() {
+        let list = vec![LispVal::Number(1), LispVal::Number(2), LispVal::Number(3)];
+
+        assert_eq!(builtin_car(vec![LispVal::List(list.clone())]).unwrap(), LispVal::Number(1));
+        assert_eq!(
+            builtin_cdr(vec![LispVal::List(list.clone())]).unwrap(),
+            LispVal::List(vec![LispVal::Number(2), LispVal::Number(3)])
+        );
+        assert_eq!(
+            builtin_cons(vec![LispVal::Number(0), LispVal::List(list)]).unwrap(),
+            LispVal::List(vec![
+                LispVal::Number(0),
+                LispVal::Number(1),
+                LispVal::Number(2),
+                LispVal::Number(3)
+            ])
+        );
+    }
+}
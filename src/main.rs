@@ -1,15 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+
 use pom::char_class::alpha;
-use pom::parser::{call, is_a, none_of, not_a, one_of, seq, sym};
+use pom::parser::{any, call, is_a, none_of, not_a, one_of, seq, sym};
 use pom::parser::{list, Parser};
 
-#[derive(Debug, PartialEq)]
+mod error;
+mod eval;
+
+use error::ParseError;
+use eval::EvalError;
+
+#[derive(Debug, Clone)]
 pub enum LispVal {
     Atom(String),
     List(Vec<LispVal>),
     DottedList(Vec<LispVal>, Box<LispVal>),
     Number(i64),
+    Float(f64),
     String(String),
     Bool(bool),
+    Char(char),
+    RustFunction(String, fn(Vec<LispVal>) -> Result<LispVal, EvalError>),
+}
+
+impl PartialEq for LispVal {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (LispVal::Atom(a), LispVal::Atom(b)) => a == b,
+            (LispVal::List(a), LispVal::List(b)) => a == b,
+            (LispVal::DottedList(a_init, a_tail), LispVal::DottedList(b_init, b_tail)) => {
+                a_init == b_init && a_tail == b_tail
+            }
+            (LispVal::Number(a), LispVal::Number(b)) => a == b,
+            (LispVal::Float(a), LispVal::Float(b)) => a == b,
+            (LispVal::String(a), LispVal::String(b)) => a == b,
+            (LispVal::Bool(a), LispVal::Bool(b)) => a == b,
+            (LispVal::Char(a), LispVal::Char(b)) => a == b,
+            // Function pointers aren't meaningfully comparable; two builtins
+            // are equal iff they were registered under the same name.
+            (LispVal::RustFunction(a, _), LispVal::RustFunction(b, _)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for LispVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LispVal::Number(n) => write!(f, "{}", n),
+            LispVal::Float(n) => write!(f, "{}", format_float(*n)),
+            LispVal::Bool(true) => write!(f, "#t"),
+            LispVal::Bool(false) => write!(f, "#f"),
+            LispVal::Atom(name) => write!(f, "{}", name),
+            LispVal::Char(c) => write!(f, "#\\{}", format_char(*c)),
+            LispVal::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        other => write!(f, "{}", other)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            LispVal::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+            LispVal::DottedList(init, tail) => {
+                write!(f, "(")?;
+                for item in init {
+                    write!(f, "{} ", item)?;
+                }
+                write!(f, ". {})", tail)
+            }
+            LispVal::RustFunction(name, _) => write!(f, "#<function {}>", name),
+        }
+    }
+}
+
+fn format_float(n: f64) -> String {
+    let rendered = format!("{}", n);
+    if rendered.contains(['.', 'e', 'E']) {
+        rendered
+    } else {
+        format!("{}.0", rendered)
+    }
+}
+
+fn format_char(c: char) -> String {
+    match c {
+        ' ' => "space".to_string(),
+        '\n' => "newline".to_string(),
+        '\r' => "return".to_string(),
+        '\t' => "tab".to_string(),
+        '\0' => "nul".to_string(),
+        other => other.to_string(),
+    }
 }
 
 pub fn letter<'a>() -> Parser<'a, u8, u8> {
@@ -38,6 +133,17 @@ pub fn string<'a>() -> Parser<'a, u8, LispVal> {
         .map(|s| LispVal::String(s))
 }
 
+pub fn char_literal<'a>() -> Parser<'a, u8, LispVal> {
+    let prefix = sym(b'#') * sym(b'\\');
+    let named_char = seq(b"space").map(|_| ' ')
+        | seq(b"newline").map(|_| '\n')
+        | seq(b"return").map(|_| '\r')
+        | seq(b"tab").map(|_| '\t')
+        | seq(b"nul").map(|_| '\0');
+    let single_char = any().map(|b| b as char);
+    (prefix * (named_char | single_char)).map(LispVal::Char)
+}
+
 pub fn atom<'a>() -> Parser<'a, u8, LispVal> {
     let first_matcher = letter() | symbol(());
     let rest_matcher = (letter() | digit() | symbol(())).repeat(0..);
@@ -54,19 +160,41 @@ pub fn number<'a>() -> Parser<'a, u8, LispVal> {
     octal_number() | binary_number() | hex_number() | decimal_number()
 }
 pub fn decimal_number<'a>() -> Parser<'a, u8, LispVal> {
-    digit().repeat(1..).collect().map(|parsed| {
-        let as_string = String::from_utf8(parsed.to_vec()).unwrap();
-        LispVal::Number(i64::from_str_radix(&as_string, 10).unwrap())
-    })
+    let sign = sym(b'-').opt();
+    let integer_part = digit().repeat(1..);
+    let fraction_part = (sym(b'.') + digit().repeat(1..)).opt();
+    let exponent_part = (one_of(b"eE") + (sym(b'+') | sym(b'-')).opt() + digit().repeat(1..)).opt();
+    (sign + integer_part + fraction_part + exponent_part)
+        .collect()
+        .convert(|parsed| {
+            let as_string = String::from_utf8_lossy(parsed).into_owned();
+            if fraction_part_or_exponent(&as_string) {
+                let value = f64::from_str(&as_string).map_err(|e| e.to_string())?;
+                if !value.is_finite() {
+                    return Err(format!("numeric literal out of range: {}", as_string));
+                }
+                Ok(LispVal::Float(value))
+            } else {
+                i64::from_str_radix(&as_string, 10)
+                    .map(LispVal::Number)
+                    .map_err(|e| e.to_string())
+            }
+        })
+}
+
+fn fraction_part_or_exponent(literal: &str) -> bool {
+    literal.contains('.') || literal.contains('e') || literal.contains('E')
 }
 
 pub fn binary_number<'a>() -> Parser<'a, u8, LispVal> {
     let prefix = sym(b'#') * sym(b'b');
     (prefix.discard() * (one_of(b"01").repeat(1..)))
         .collect()
-        .map(|parsed| {
+        .convert(|parsed| {
             let as_string = String::from_utf8_lossy(&parsed[2..]);
-            LispVal::Number(i64::from_str_radix(&as_string, 2).unwrap())
+            i64::from_str_radix(&as_string, 2)
+                .map(LispVal::Number)
+                .map_err(|e| e.to_string())
         })
 }
 
@@ -74,9 +202,11 @@ pub fn octal_number<'a>() -> Parser<'a, u8, LispVal> {
     let prefix = sym(b'#') * sym(b'o');
     (prefix.discard() * (one_of(b"01234567").repeat(1..)))
         .collect()
-        .map(|parsed| {
+        .convert(|parsed| {
             let as_string = String::from_utf8_lossy(&parsed[2..]);
-            LispVal::Number(i64::from_str_radix(&as_string, 8).unwrap())
+            i64::from_str_radix(&as_string, 8)
+                .map(LispVal::Number)
+                .map_err(|e| e.to_string())
         })
 }
 
@@ -84,10 +214,11 @@ pub fn hex_number<'a>() -> Parser<'a, u8, LispVal> {
     let prefix = sym(b'#') * sym(b'x');
     (prefix.discard() * (one_of(b"0123456789abcdefABCDEF").repeat(1..)))
         .collect()
-        .map(|parsed| {
-            let mut as_string = String::from_utf8_lossy(&parsed[2..]);
-            as_string.to_lowercase();
-            LispVal::Number(i64::from_str_radix(&as_string, 16).unwrap())
+        .convert(|parsed| {
+            let as_string = String::from_utf8_lossy(&parsed[2..]).to_lowercase();
+            i64::from_str_radix(&as_string, 16)
+                .map(LispVal::Number)
+                .map_err(|e| e.to_string())
         })
 }
 
@@ -100,19 +231,79 @@ pub fn parse_list<'a>() -> Parser<'a, u8, Vec<LispVal>> {
     (sym(b'(') * list(call(parse_expr), whitespace())) - sym(b')')
 }
 
+pub fn dotted_list<'a>() -> Parser<'a, u8, LispVal> {
+    let init = list(call(parse_expr), whitespace());
+    (sym(b'(') * whitespace() * init - whitespace() - sym(b'.') - whitespace() + call(parse_expr)
+        - whitespace()
+        - sym(b')'))
+        .map(|(init, tail)| LispVal::DottedList(init, Box::new(tail)))
+}
+
+pub fn quoted<'a>() -> Parser<'a, u8, LispVal> {
+    (sym(b'\'') * call(parse_expr))
+        .map(|expr| LispVal::List(vec![LispVal::Atom("quote".to_string()), expr]))
+}
+
+pub fn quasiquoted<'a>() -> Parser<'a, u8, LispVal> {
+    (sym(b'`') * call(parse_expr))
+        .map(|expr| LispVal::List(vec![LispVal::Atom("quasiquote".to_string()), expr]))
+}
+
+pub fn unquoted<'a>() -> Parser<'a, u8, LispVal> {
+    (sym(b',') * call(parse_expr))
+        .map(|expr| LispVal::List(vec![LispVal::Atom("unquote".to_string()), expr]))
+}
+
 pub fn parse_expr<'a>() -> Parser<'a, u8, LispVal> {
-    number() | atom() | string()
+    number()
+        | quoted()
+        | quasiquoted()
+        | unquoted()
+        | string()
+        | char_literal()
+        | atom()
+        | dotted_list()
+        | parse_list().map(LispVal::List)
 }
 
-pub fn read_expr(input: &[u8]) -> LispVal {
-    parse_expr().parse(input).unwrap()
+pub fn read_expr(input: &[u8]) -> Result<LispVal, ParseError> {
+    parse_expr().parse(input).map_err(ParseError::from)
 }
 
-pub fn read_list(input: &[u8]) -> LispVal {
-    LispVal::List(parse_list().parse(input).unwrap())
+pub fn read_list(input: &[u8]) -> Result<LispVal, ParseError> {
+    parse_list()
+        .parse(input)
+        .map(LispVal::List)
+        .map_err(ParseError::from)
 }
 
-fn main() {}
+fn main() {
+    use std::io::{self, BufRead, Write};
+
+    let mut state = eval::LispState::new();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    print!("> ");
+    stdout.flush().ok();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        if !line.trim().is_empty() {
+            match read_expr(line.as_bytes()) {
+                Ok(expr) => match state.eval(&expr) {
+                    Ok(value) => println!("{}", value),
+                    Err(err) => println!("error: {}", err),
+                },
+                Err(err) => println!("error: {}", err),
+            }
+        }
+        print!("> ");
+        stdout.flush().ok();
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -120,36 +311,56 @@ mod test {
 
     #[test]
     fn read_number() {
-        assert_eq!(read_expr(b"123"), LispVal::Number(123));
+        assert_eq!(read_expr(b"123").unwrap(), LispVal::Number(123));
     }
 
     #[test]
     fn read_binary_number() {
-        assert_eq!(read_expr(b"#b11"), LispVal::Number(3));
+        assert_eq!(read_expr(b"#b11").unwrap(), LispVal::Number(3));
     }
 
     #[test]
     fn read_octal_number() {
-        assert_eq!(read_expr(b"#o321"), LispVal::Number(209));
+        assert_eq!(read_expr(b"#o321").unwrap(), LispVal::Number(209));
     }
     #[test]
     fn read_hex_number() {
-        assert_eq!(read_expr(b"#xFF"), LispVal::Number(255));
+        assert_eq!(read_expr(b"#xFF").unwrap(), LispVal::Number(255));
+    }
+
+    #[test]
+    fn read_negative_number() {
+        assert_eq!(read_expr(b"-5").unwrap(), LispVal::Number(-5));
+    }
+
+    #[test]
+    fn read_float() {
+        assert_eq!(read_expr(b"2.5").unwrap(), LispVal::Float(2.5));
+    }
+
+    #[test]
+    fn read_negative_float() {
+        assert_eq!(read_expr(b"-0.5").unwrap(), LispVal::Float(-0.5));
+    }
+
+    #[test]
+    fn read_float_with_exponent() {
+        assert_eq!(read_expr(b"1.0e10").unwrap(), LispVal::Float(1.0e10));
     }
 
     #[test]
     fn read_string() {
-        assert_eq!(read_expr(b"\"123\""), LispVal::String("123".to_owned()));
+        assert_eq!(read_expr(b"\"123\"").unwrap(), LispVal::String("123".to_owned()));
     }
 
     #[test]
     fn read_atom() {
-        assert_eq!(read_expr(b"symbol"), LispVal::Atom("symbol".to_owned()));
+        assert_eq!(read_expr(b"symbol").unwrap(), LispVal::Atom("symbol".to_owned()));
     }
     #[test]
     fn read_string_with_quote() {
         assert_eq!(
-            read_expr(b"\"1\\\"23\""),
+            read_expr(b"\"1\\\"23\"").unwrap(),
             LispVal::String("1\"23".to_owned())
         );
     }
@@ -157,7 +368,7 @@ mod test {
     #[test]
     fn read_list_test() {
         assert_eq!(
-            read_list(b"(1 2 3)"),
+            read_list(b"(1 2 3)").unwrap(),
             LispVal::List(vec![
                 LispVal::Number(1),
                 LispVal::Number(2),
@@ -168,7 +379,7 @@ mod test {
     #[test]
     fn read_list_strings() {
         assert_eq!(
-            read_list(b"(1 2 \"Hello World\")"),
+            read_list(b"(1 2 \"Hello World\")").unwrap(),
             LispVal::List(vec![
                 LispVal::Number(1),
                 LispVal::Number(2),
@@ -176,4 +387,129 @@ mod test {
             ])
         );
     }
+
+    #[test]
+    fn read_expr_malformed_input_is_an_error() {
+        assert!(read_expr(b"").is_err());
+    }
+
+    #[test]
+    fn read_list_unclosed_paren_is_an_error() {
+        assert!(read_list(b"(1 2 3").is_err());
+    }
+
+    #[test]
+    fn read_expr_nested_list() {
+        assert_eq!(
+            read_expr(b"(1 (2 3) 4)").unwrap(),
+            LispVal::List(vec![
+                LispVal::Number(1),
+                LispVal::List(vec![LispVal::Number(2), LispVal::Number(3)]),
+                LispVal::Number(4)
+            ])
+        );
+    }
+
+    #[test]
+    fn read_expr_dotted_list() {
+        assert_eq!(
+            read_expr(b"(1 2 . 3)").unwrap(),
+            LispVal::DottedList(
+                vec![LispVal::Number(1), LispVal::Number(2)],
+                Box::new(LispVal::Number(3))
+            )
+        );
+    }
+
+    #[test]
+    fn read_expr_quote_sugar() {
+        assert_eq!(
+            read_expr(b"'x").unwrap(),
+            LispVal::List(vec![LispVal::Atom("quote".to_owned()), LispVal::Atom("x".to_owned())])
+        );
+    }
+
+    #[test]
+    fn read_expr_quasiquote_sugar() {
+        assert_eq!(
+            read_expr(b"`x").unwrap(),
+            LispVal::List(vec![
+                LispVal::Atom("quasiquote".to_owned()),
+                LispVal::Atom("x".to_owned())
+            ])
+        );
+    }
+
+    #[test]
+    fn read_char_literal() {
+        assert_eq!(read_expr(b"#\\a").unwrap(), LispVal::Char('a'));
+    }
+
+    #[test]
+    fn read_named_char_literals() {
+        assert_eq!(read_expr(b"#\\space").unwrap(), LispVal::Char(' '));
+        assert_eq!(read_expr(b"#\\newline").unwrap(), LispVal::Char('\n'));
+        assert_eq!(read_expr(b"#\\tab").unwrap(), LispVal::Char('\t'));
+    }
+
+    #[test]
+    fn read_char_literal_does_not_break_list_parsing() {
+        assert_eq!(
+            read_expr(b"(#\\( #\\))").unwrap(),
+            LispVal::List(vec![LispVal::Char('('), LispVal::Char(')')])
+        );
+    }
+
+    #[test]
+    fn read_expr_unquote_sugar() {
+        assert_eq!(
+            read_expr(b",x").unwrap(),
+            LispVal::List(vec![LispVal::Atom("unquote".to_owned()), LispVal::Atom("x".to_owned())])
+        );
+    }
+
+    #[test]
+    fn display_atoms_and_literals() {
+        assert_eq!(LispVal::Number(42).to_string(), "42");
+        assert_eq!(LispVal::Float(3.5).to_string(), "3.5");
+        assert_eq!(LispVal::Float(3.0).to_string(), "3.0");
+        assert_eq!(LispVal::Bool(true).to_string(), "#t");
+        assert_eq!(LispVal::Bool(false).to_string(), "#f");
+        assert_eq!(LispVal::Atom("foo".to_owned()).to_string(), "foo");
+        assert_eq!(LispVal::Char('a').to_string(), "#\\a");
+        assert_eq!(LispVal::Char(' ').to_string(), "#\\space");
+        assert_eq!(
+            LispVal::String("he said \"hi\"".to_owned()).to_string(),
+            "\"he said \\\"hi\\\"\""
+        );
+    }
+
+    #[test]
+    fn display_list_and_dotted_list() {
+        let list = LispVal::List(vec![LispVal::Number(1), LispVal::Number(2), LispVal::Number(3)]);
+        assert_eq!(list.to_string(), "(1 2 3)");
+
+        let dotted = LispVal::DottedList(
+            vec![LispVal::Number(1), LispVal::Number(2)],
+            Box::new(LispVal::Number(3)),
+        );
+        assert_eq!(dotted.to_string(), "(1 2 . 3)");
+    }
+
+    #[test]
+    fn display_round_trips_through_read_expr() {
+        let values = vec![
+            LispVal::Number(-7),
+            LispVal::Float(2.5),
+            LispVal::Bool(true),
+            LispVal::Atom("foo".to_owned()),
+            LispVal::Char('z'),
+            LispVal::List(vec![LispVal::Number(1), LispVal::Atom("bar".to_owned())]),
+            LispVal::DottedList(vec![LispVal::Number(1)], Box::new(LispVal::Number(2))),
+        ];
+        for value in values {
+            let rendered = value.to_string();
+            assert_eq!(read_expr(rendered.as_bytes()).unwrap(), value);
+        }
+    }
 }